@@ -0,0 +1,53 @@
+use std::path::PathBuf;
+
+use crate::cache::Cache;
+
+/// Directory (relative to `app_data_dir`) that remote assets are cached under.
+/// Nested inside `cache/`, so the version migration that removes `cache/`
+/// removes it too; freshness eviction checks it separately, with its own
+/// max age (see `evict_stale_caches`).
+const ASSETS_SUBDIR: &str = "cache/assets";
+
+/// Returns the local cache path for a remote URL, fetching and storing it
+/// first if it isn't already cached.
+///
+/// The cache key is the MD5 digest of the full URL string (query string
+/// included), so differing query parameters never collide on the same file.
+#[tauri::command]
+pub async fn cache_remote_asset(app: tauri::AppHandle, url: String) -> Result<String, String> {
+    let data_dir = crate::resolve_data_dir(&app)?;
+
+    let assets_dir = data_dir.join(ASSETS_SUBDIR);
+    let digest = format!("{:x}", md5::compute(url.as_bytes()));
+    let asset_path = assets_dir.join(&digest);
+
+    if asset_path.exists() {
+        return Ok(asset_path.to_string_lossy().into_owned());
+    }
+
+    let response = reqwest::get(&url)
+        .await
+        .map_err(|e| format!("failed to fetch '{url}': {e}"))?
+        .error_for_status()
+        .map_err(|e| format!("remote asset '{url}' returned an error: {e}"))?;
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| format!("failed to read body for '{url}': {e}"))?;
+
+    Cache::new(assets_dir.clone())
+        .ensure_dir()
+        .map_err(|e| format!("failed to create assets cache dir: {e}"))?;
+
+    // Write to a temp file in the same directory, then rename, so a reader
+    // never observes a partially-written cache entry.
+    let tmp_path: PathBuf = assets_dir.join(format!("{digest}.part"));
+    tokio::fs::write(&tmp_path, &bytes)
+        .await
+        .map_err(|e| format!("failed to write cached asset: {e}"))?;
+    tokio::fs::rename(&tmp_path, &asset_path)
+        .await
+        .map_err(|e| format!("failed to finalize cached asset: {e}"))?;
+
+    Ok(asset_path.to_string_lossy().into_owned())
+}
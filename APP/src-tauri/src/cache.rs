@@ -0,0 +1,218 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use walkdir::WalkDir;
+
+use crate::{get_version_file_path, resolve_data_dir, APP_VERSION, KNOWN_CACHE_DIRS};
+
+/// Default staleness threshold for a cache subdirectory: one day.
+pub const DEFAULT_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How fresh a cache subdirectory is, relative to a configured max age.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheFreshness {
+    /// The newest file in the directory is within `max_age`.
+    Fresh,
+    /// The newest file in the directory is older than `max_age` by the given amount.
+    Stale(Duration),
+    /// The directory doesn't exist, or exists but contains no files.
+    Missing,
+}
+
+/// A single cache subdirectory under `app_data_dir`, with an age budget.
+///
+/// Freshness checks never create the directory — only [`Cache::ensure_dir`]
+/// does, and only right before something is about to be written, so a
+/// read-only freshness check never materializes empty dirs.
+pub struct Cache {
+    dir: PathBuf,
+    max_age: Duration,
+    exclude: Vec<PathBuf>,
+}
+
+impl Cache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir, max_age: DEFAULT_MAX_AGE, exclude: Vec::new() }
+    }
+
+    pub fn with_max_age(dir: PathBuf, max_age: Duration) -> Self {
+        Self { dir, max_age, exclude: Vec::new() }
+    }
+
+    /// Excludes a subdirectory (given relative to `dir`) from freshness,
+    /// usage, and clearing — for a cache dir that nests another cache dir
+    /// tracked separately, so the nested one is never counted or removed
+    /// as part of the parent.
+    pub fn excluding(mut self, relative_subdir: &str) -> Self {
+        self.exclude.push(self.dir.join(relative_subdir));
+        self
+    }
+
+    fn is_excluded(&self, path: &std::path::Path) -> bool {
+        self.exclude.iter().any(|excluded| path.starts_with(excluded))
+    }
+
+    /// Removes the cache directory's contents, leaving any excluded
+    /// subdirectories untouched. If there's nothing excluded, this removes
+    /// `dir` itself; otherwise it removes only `dir`'s non-excluded direct
+    /// children, so a nested cache with its own budget survives.
+    pub fn clear(&self) -> io::Result<()> {
+        if !self.dir.exists() {
+            return Ok(());
+        }
+
+        if self.exclude.is_empty() {
+            return fs::remove_dir_all(&self.dir);
+        }
+
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if self.is_excluded(&path) {
+                continue;
+            }
+            if path.is_dir() {
+                fs::remove_dir_all(&path)?;
+            } else {
+                fs::remove_file(&path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Ensures the cache directory exists, creating it (and its parents) on demand.
+    pub fn ensure_dir(&self) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)
+    }
+
+    /// Computes freshness by walking the directory for the most recently
+    /// modified file and comparing its age against `max_age`.
+    pub fn freshness(&self) -> CacheFreshness {
+        let Some(newest) = self.newest_mtime() else {
+            return CacheFreshness::Missing;
+        };
+
+        match SystemTime::now().duration_since(newest) {
+            Ok(age) if age > self.max_age => CacheFreshness::Stale(age - self.max_age),
+            Ok(_) => CacheFreshness::Fresh,
+            Err(_) => CacheFreshness::Fresh, // clock skew put mtime in the future; treat as fresh
+        }
+    }
+
+    fn newest_mtime(&self) -> Option<SystemTime> {
+        if !self.dir.exists() {
+            return None;
+        }
+
+        WalkDir::new(&self.dir)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .filter(|entry| !self.is_excluded(entry.path()))
+            .filter_map(|entry| entry.metadata().ok()?.modified().ok())
+            .max()
+    }
+
+    /// Sums file sizes and counts files, for reporting rather than eviction.
+    fn usage(&self) -> (u64, u64) {
+        if !self.dir.exists() {
+            return (0, 0);
+        }
+
+        WalkDir::new(&self.dir)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .filter(|entry| !self.is_excluded(entry.path()))
+            .filter_map(|entry| entry.metadata().ok())
+            .fold((0u64, 0u64), |(size, count), metadata| {
+                (size + metadata.len(), count + 1)
+            })
+    }
+}
+
+/// Usage summary for a single cache subdirectory, as returned by `cache_info`.
+#[derive(Debug, Serialize)]
+pub struct CacheDirInfo {
+    name: &'static str,
+    size_bytes: u64,
+    file_count: u64,
+    newest_mtime_secs: Option<u64>,
+}
+
+/// Full cache report returned by `cache_info`: per-directory usage plus the
+/// version the migration system is tracking.
+#[derive(Debug, Serialize)]
+pub struct CacheReport {
+    dirs: Vec<CacheDirInfo>,
+    stored_version: String,
+    current_version: String,
+}
+
+/// Reports size, file count, and newest mtime for every known cache
+/// subdirectory, plus the stored vs current app version, so a settings
+/// screen can show users how much space each cache uses.
+#[tauri::command]
+pub fn cache_info(app: tauri::AppHandle) -> Result<CacheReport, String> {
+    let data_dir = resolve_data_dir(&app)?;
+
+    let dirs = KNOWN_CACHE_DIRS
+        .iter()
+        .map(|(name, relative_path)| {
+            let mut cache = Cache::new(data_dir.join(relative_path));
+            // `assets` nests under `cache` and is reported as its own entry,
+            // so exclude it here to avoid double-counting its bytes/files.
+            if *name == "cache" {
+                cache = cache.excluding("assets");
+            }
+            let (size_bytes, file_count) = cache.usage();
+            let newest_mtime_secs = cache
+                .newest_mtime()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|duration| duration.as_secs());
+
+            CacheDirInfo { name, size_bytes, file_count, newest_mtime_secs }
+        })
+        .collect();
+
+    let stored_version = get_version_file_path(&app)
+        .map(|path| fs::read_to_string(path).unwrap_or_default())
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+
+    Ok(CacheReport { dirs, stored_version, current_version: APP_VERSION.to_string() })
+}
+
+/// Clears the given cache subdirectories (by the names reported by
+/// `cache_info`), or every known one if `dirs` is `None`; names not in
+/// `KNOWN_CACHE_DIRS` are ignored. Shares `resolve_data_dir` with `cache_info`
+/// and the automatic version/freshness clears, and `KNOWN_CACHE_DIRS` with
+/// `cache_info`. The automatic clears name their directories themselves and
+/// do not read `KNOWN_CACHE_DIRS`.
+#[tauri::command]
+pub fn clear_cache(app: tauri::AppHandle, dirs: Option<Vec<String>>) -> Result<(), String> {
+    let data_dir = resolve_data_dir(&app)?;
+
+    let selected: Vec<&(&str, &str)> = match &dirs {
+        Some(names) => KNOWN_CACHE_DIRS
+            .iter()
+            .filter(|(name, _)| names.iter().any(|requested| requested == name))
+            .collect(),
+        None => KNOWN_CACHE_DIRS.iter().collect(),
+    };
+
+    for (name, relative_path) in selected {
+        let mut cache = Cache::new(data_dir.join(relative_path));
+        // Keep this in sync with `cache_info`: `assets` is reported and
+        // cleared separately, so clearing `cache` must not take it along.
+        if *name == "cache" {
+            cache = cache.excluding("assets");
+        }
+        cache.clear().map_err(|e| format!("failed to clear '{name}' cache: {e}"))?;
+    }
+
+    Ok(())
+}
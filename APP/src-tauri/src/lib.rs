@@ -1,63 +1,126 @@
+mod assets;
+mod cache;
+mod migrations;
+mod update;
+
+use cache::{Cache, CacheFreshness};
 use tauri::Manager;
 use std::fs;
 use std::path::PathBuf;
+use std::time::Duration;
+
+/// Cached assets (keyed by the MD5 digest of their URL) are given a longer
+/// budget than the rest of `cache` before being swept for disk space.
+const ASSET_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Every cache subdirectory known to `cache_info` and `clear_cache`, as
+/// (display name, path relative to `app_data_dir`). `assets` lives nested
+/// under `cache/`, so the version migration that removes `cache/` removes it
+/// too; it is reported, age-evicted and manually cleared as its own entry.
+pub(crate) const KNOWN_CACHE_DIRS: &[(&str, &str)] =
+    &[("webview", "webview"), ("cache", "cache"), ("assets", "cache/assets")];
 
 const APP_VERSION: &str = "2.0.0";
 
-fn get_version_file_path(app: &tauri::AppHandle) -> Option<PathBuf> {
-    app.path().app_data_dir().ok().map(|dir| dir.join(".app_version"))
+/// Resolves `app_data_dir`, the single path-resolution point shared by every
+/// automatic and manual cache operation so their error messages stay consistent.
+pub(crate) fn resolve_data_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map_err(|e| format!("could not resolve app data dir: {e}"))
+}
+
+pub(crate) fn get_version_file_path(app: &tauri::AppHandle) -> Option<PathBuf> {
+    resolve_data_dir(app).ok().map(|dir| dir.join(".app_version"))
 }
 
 fn check_and_clear_cache_if_needed(app: &tauri::AppHandle) {
-    if let Some(version_file) = get_version_file_path(app) {
-        let stored_version = fs::read_to_string(&version_file).unwrap_or_default();
-        let stored_version = stored_version.trim();
-        
-        // Clear cache if version changed OR if no version file exists (fresh install after uninstall)
-        if stored_version.is_empty() || stored_version != APP_VERSION {
-            let version_label = if stored_version.is_empty() {
-                "none (fresh install or old cache)".to_string()
-            } else {
-                format!("'{}'", stored_version)
-            };
-            
-            println!("Version changed from {} to '{}', clearing all cache", version_label, APP_VERSION);
-            
-            // Clear ALL cache directories in AppData
-            if let Ok(data_dir) = app.path().app_data_dir() {
-                // Clear webview cache (includes localStorage, IndexedDB, etc)
-                let webview_dir = data_dir.join("webview");
-                if webview_dir.exists() {
-                    match fs::remove_dir_all(&webview_dir) {
-                        Ok(_) => println!("✓ Cleared webview cache directory"),
-                        Err(e) => println!("✗ Failed to clear webview cache: {}", e),
-                    }
-                }
-                
-                // Clear any other cache directories
-                let cache_dir = data_dir.join("cache");
-                if cache_dir.exists() {
-                    let _ = fs::remove_dir_all(&cache_dir);
-                    println!("✓ Cleared general cache directory");
-                }
-            }
-            
-            // Ensure parent directory exists before writing
+    let Some(version_file) = get_version_file_path(app) else {
+        return;
+    };
+    let Ok(data_dir) = resolve_data_dir(app) else {
+        return;
+    };
+
+    let Some(current) = migrations::parse_version(APP_VERSION) else {
+        println!("✗ APP_VERSION '{}' is not valid semver, skipping migrations", APP_VERSION);
+        return;
+    };
+
+    let stored_raw = fs::read_to_string(&version_file).unwrap_or_default();
+    let stored_raw = stored_raw.trim();
+
+    // A stored version that fails to parse (corrupted file, manual edit) is
+    // treated the same as a fresh install rather than skipped indefinitely,
+    // so the app self-heals instead of being stranded on every launch.
+    let stored = migrations::parse_version(stored_raw).unwrap_or_else(|| {
+        println!(
+            "✗ Stored version '{}' is not valid semver, treating as fresh install",
+            stored_raw
+        );
+        migrations::parse_version("").expect("empty string always parses")
+    });
+
+    if stored > current {
+        println!(
+            "Stored version '{}' is newer than running version '{}' (downgrade), skipping migrations",
+            stored, current
+        );
+        return;
+    }
+
+    if stored == current {
+        println!("App version {} is current, no migrations needed", APP_VERSION);
+        return;
+    }
+
+    println!("Upgrading cache from '{}' to '{}'", stored, current);
+    match migrations::run(&data_dir, &stored, &current) {
+        Ok(()) => {
             if let Some(parent) = version_file.parent() {
                 let _ = fs::create_dir_all(parent);
             }
-            
-            // Store new version
             match fs::write(&version_file, APP_VERSION) {
                 Ok(_) => println!("✓ Version file updated to {}", APP_VERSION),
                 Err(e) => println!("✗ Failed to write version file: {}", e),
             }
-        } else {
-            println!("App version {} is current, no cache clear needed", APP_VERSION);
+        }
+        Err(e) => {
+            println!("✗ Migration failed, leaving stored version at '{}': {}", stored, e);
         }
     }
 }
 
+/// Evicts cache subdirectories whose newest file is older than their max age,
+/// independently of the version check. This lets server-side assets refresh
+/// on a schedule without forcing an `APP_VERSION` bump. `webview` holds user
+/// data (localStorage, IndexedDB — saved certificates, settings) and is
+/// deliberately not touched here: it's cleared only by the version-migration
+/// path or an explicit `clear_cache` call, never age-evicted, so a few idle
+/// days never costs the user their data.
+fn evict_stale_caches(app: &tauri::AppHandle) {
+    let Ok(data_dir) = resolve_data_dir(app) else {
+        return;
+    };
+
+    // `assets` is reported and evicted on its own below with a longer budget,
+    // so it's excluded here — both from the freshness measurement and from
+    // removal, so a stale non-asset file in `cache` never takes the
+    // separately-budgeted assets down with it.
+    evict_if_stale("cache", Cache::new(data_dir.join("cache")).excluding("assets"));
+    evict_if_stale("assets", Cache::with_max_age(data_dir.join("cache/assets"), ASSET_MAX_AGE));
+}
+
+fn evict_if_stale(name: &str, cache: Cache) {
+    match cache.freshness() {
+        CacheFreshness::Stale(overage) => match cache.clear() {
+            Ok(_) => println!("✓ Evicted stale '{}' cache ({}s past max age)", name, overage.as_secs()),
+            Err(e) => println!("✗ Failed to evict stale '{}' cache: {}", name, e),
+        },
+        CacheFreshness::Fresh | CacheFreshness::Missing => {}
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
@@ -65,7 +128,14 @@ pub fn run() {
         .setup(|app| {
             // Check version and clear cache if needed
             check_and_clear_cache_if_needed(&app.handle());
-            
+            evict_stale_caches(&app.handle());
+
+            // Check for a newer release in the background; never blocks startup.
+            if let Some(current) = migrations::parse_version(APP_VERSION) {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(update::check_for_update(app_handle, current));
+            }
+
             #[cfg(debug_assertions)]
             {
                 #[cfg(not(mobile))]
@@ -75,6 +145,12 @@ pub fn run() {
             }
             Ok(())
         })
+        .invoke_handler(tauri::generate_handler![
+            assets::cache_remote_asset,
+            update::download_update,
+            cache::cache_info,
+            cache::clear_cache
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -0,0 +1,75 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use semver::Version;
+
+/// A single upgrade step, tagged with the version it brings the app *to*.
+///
+/// Migrations run in ascending `target` order and are applied in place on
+/// `app_data_dir`. A migration should be idempotent where possible, since a
+/// partially-applied upgrade (e.g. the process is killed mid-run) may see
+/// the same step attempted again on the next launch.
+pub struct Migration {
+    pub target: Version,
+    pub describe: &'static str,
+    pub apply: fn(&Path) -> io::Result<()>,
+}
+
+/// The ordered set of migrations known to this build, oldest target first.
+///
+/// New steps should be appended here as new versions ship; nothing removes
+/// or reorders earlier entries, so upgrading from any older stored version
+/// still replays every step it hasn't seen yet.
+pub fn registry() -> Vec<Migration> {
+    vec![Migration {
+        target: Version::new(2, 0, 0),
+        describe: "clear webview and general cache directories",
+        apply: |data_dir| {
+            let webview_dir = data_dir.join("webview");
+            if webview_dir.exists() {
+                fs::remove_dir_all(&webview_dir)?;
+                println!("✓ Cleared webview cache directory");
+            }
+
+            let cache_dir = data_dir.join("cache");
+            if cache_dir.exists() {
+                fs::remove_dir_all(&cache_dir)?;
+                println!("✓ Cleared general cache directory");
+            }
+
+            Ok(())
+        },
+    }]
+}
+
+/// Parses a stored or built-in version string as semver, treating an empty
+/// string (fresh install, or cache predating versioned migrations) as
+/// `0.0.0` so every registered migration applies.
+pub fn parse_version(raw: &str) -> Option<Version> {
+    if raw.is_empty() {
+        return Some(Version::new(0, 0, 0));
+    }
+    Version::parse(raw).ok()
+}
+
+/// Runs every migration whose target is greater than `stored` and
+/// less-than-or-equal to `current`, in ascending order.
+///
+/// Returns `Ok(())` once all applicable migrations succeed; the caller only
+/// persists the new stored version after that, so a failure partway through
+/// leaves the stored version at its original value and every applicable
+/// migration (including ones already applied here) replays on the next
+/// launch. Each migration step should tolerate being re-applied.
+pub fn run(data_dir: &Path, stored: &Version, current: &Version) -> io::Result<()> {
+    for migration in registry() {
+        if migration.target > *stored && migration.target <= *current {
+            println!(
+                "Running migration to {}: {}",
+                migration.target, migration.describe
+            );
+            (migration.apply)(data_dir)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, Manager};
+
+use crate::cache::Cache;
+
+/// Endpoint serving the current release manifest for this app.
+const MANIFEST_URL: &str = "https://certify-app.example.com/releases/manifest.json";
+
+/// How long to wait for the manifest before giving up for this launch.
+const MANIFEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Event emitted to the main window when a newer release is available.
+const UPDATE_AVAILABLE_EVENT: &str = "update-available";
+
+/// Event emitted (repeatedly) while `download_update` is streaming the installer.
+const UPDATE_PROGRESS_EVENT: &str = "update-download-progress";
+
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    version: String,
+    notes: String,
+    url: String,
+}
+
+/// Payload for [`UPDATE_AVAILABLE_EVENT`].
+#[derive(Debug, Clone, Serialize)]
+struct UpdateAvailable {
+    version: String,
+    notes: String,
+    url: String,
+}
+
+/// Payload for [`UPDATE_PROGRESS_EVENT`].
+#[derive(Debug, Clone, Serialize)]
+struct DownloadProgress {
+    downloaded: u64,
+    total: Option<u64>,
+}
+
+/// Fetches the remote manifest and, if it advertises a version newer than
+/// `current`, emits [`UPDATE_AVAILABLE_EVENT`] to the main webview window.
+///
+/// Runs on its own async task off the `setup` hook; any failure (offline,
+/// timeout, bad manifest) is logged and otherwise ignored so a broken update
+/// check never blocks or delays startup.
+pub async fn check_for_update(app: tauri::AppHandle, current: Version) {
+    let manifest = match fetch_manifest().await {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            println!("✗ Update check failed: {e}");
+            return;
+        }
+    };
+
+    let Some(remote_version) = crate::migrations::parse_version(&manifest.version) else {
+        println!("✗ Update manifest had an unparseable version '{}'", manifest.version);
+        return;
+    };
+
+    if remote_version <= current {
+        println!("App is up to date (remote '{}', local '{}')", remote_version, current);
+        return;
+    }
+
+    println!("Update available: '{}' -> '{}'", current, remote_version);
+    let payload = UpdateAvailable {
+        version: manifest.version,
+        notes: manifest.notes,
+        url: manifest.url,
+    };
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.emit(UPDATE_AVAILABLE_EVENT, payload);
+    }
+}
+
+async fn fetch_manifest() -> Result<Manifest, String> {
+    let client = reqwest::Client::builder()
+        .timeout(MANIFEST_TIMEOUT)
+        .build()
+        .map_err(|e| format!("could not build http client: {e}"))?;
+
+    client
+        .get(MANIFEST_URL)
+        .send()
+        .await
+        .map_err(|e| format!("could not reach '{MANIFEST_URL}': {e}"))?
+        .error_for_status()
+        .map_err(|e| format!("manifest endpoint returned an error: {e}"))?
+        .json::<Manifest>()
+        .await
+        .map_err(|e| format!("could not parse manifest: {e}"))
+}
+
+/// Streams the installer at `url` into the cache dir, emitting
+/// [`UPDATE_PROGRESS_EVENT`] as bytes arrive, and returns the local path once
+/// the download completes.
+#[tauri::command]
+pub async fn download_update(app: tauri::AppHandle, url: String) -> Result<String, String> {
+    let data_dir = crate::resolve_data_dir(&app)?;
+    let downloads_dir = data_dir.join("cache").join("updates");
+    Cache::new(downloads_dir.clone())
+        .ensure_dir()
+        .map_err(|e| format!("failed to create updates cache dir: {e}"))?;
+
+    let file_name = url
+        .rsplit('/')
+        .next()
+        .filter(|name| !name.is_empty())
+        .unwrap_or("update.bin");
+    let dest_path = downloads_dir.join(file_name);
+    let tmp_path = downloads_dir.join(format!("{file_name}.part"));
+
+    let response = reqwest::get(&url)
+        .await
+        .map_err(|e| format!("failed to start download from '{url}': {e}"))?
+        .error_for_status()
+        .map_err(|e| format!("update server returned an error: {e}"))?;
+    let total = response.content_length();
+
+    let mut file = tokio::fs::File::create(&tmp_path)
+        .await
+        .map_err(|e| format!("failed to create download file: {e}"))?;
+    let mut downloaded: u64 = 0;
+    let mut stream = response.bytes_stream();
+
+    use tokio::io::AsyncWriteExt;
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| format!("download interrupted: {e}"))?;
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| format!("failed to write download chunk: {e}"))?;
+        downloaded += chunk.len() as u64;
+
+        let _ = app.emit(UPDATE_PROGRESS_EVENT, DownloadProgress { downloaded, total });
+    }
+
+    tokio::fs::rename(&tmp_path, &dest_path)
+        .await
+        .map_err(|e| format!("failed to finalize downloaded update: {e}"))?;
+
+    Ok(dest_path.to_string_lossy().into_owned())
+}